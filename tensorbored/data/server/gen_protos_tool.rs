@@ -31,6 +31,43 @@ fn main() -> std::io::Result<()> {
     // Generate `bytes::Bytes` struct fields for all `bytes` protobuf fields in the `tensorbored`
     // package.
     prost_config.bytes(&[".tensorbored"]);
+    // Under the `json-api` feature, the frontend talks to this crate over JSON HTTP rather
+    // than gRPC, so the generated structs and enums need to round-trip through `serde_json`.
+    // prost represents both `bytes` and `enum` fields as plain types with no `serde` impl of
+    // their own (`bytes::Bytes`, and a bare `i32` for enums) or the wrong JSON shape (the
+    // wire number rather than the protobuf enum name), so each such field needs its own
+    // `field_attribute` pinning it to a `crate::json_api` helper.
+    if std::env::var_os("CARGO_FEATURE_JSON_API").is_some() {
+        prost_config.type_attribute(
+            ".tensorbored",
+            "#[derive(serde::Serialize, serde::Deserialize)]",
+        );
+        for bytes_field in [
+            ".tensorbored.TensorProto.tensor_content",
+            ".tensorbored.Summary.Image.encoded_image_string",
+            ".tensorbored.Summary.Audio.encoded_audio_string",
+            ".tensorbored.Event.graph_def",
+            ".tensorbored.Event.meta_graph_def",
+            ".tensorbored.SummaryMetadata.PluginData.content",
+        ] {
+            prost_config.field_attribute(
+                bytes_field,
+                "#[serde(with = \"crate::json_api::base64_bytes\")]",
+            );
+        }
+        // `repeated bytes` fields generate a `Vec<bytes::Bytes>`, which the scalar
+        // `base64_bytes` adapter can't drive; they need the `Vec`-shaped counterpart.
+        for repeated_bytes_field in [".tensorbored.TensorProto.string_val"] {
+            prost_config.field_attribute(
+                repeated_bytes_field,
+                "#[serde(with = \"crate::json_api::base64_bytes_vec\")]",
+            );
+        }
+        prost_config.field_attribute(
+            ".tensorbored.TensorProto.dtype",
+            "#[serde(with = \"crate::json_api::data_type_name\")]",
+        );
+    }
     tonic_build::configure()
         .out_dir(&out_dir)
         .file_descriptor_set_path(&file_descriptor)
@@ -39,9 +76,20 @@ fn main() -> std::io::Result<()> {
             prost_config,
             &[
                 "tensorbored/compat/proto/event.proto",
+                "tensorbored/compat/proto/summary.proto",
+                "tensorbored/compat/proto/tensor.proto",
                 "tensorbored/data/proto/data_provider.proto",
                 "tensorbored/plugins/audio/plugin_data.proto",
                 "tensorbored/plugins/image/plugin_data.proto",
+                "tensorbored/plugins/scalar/plugin_data.proto",
+                "tensorbored/plugins/histogram/plugin_data.proto",
+                "tensorbored/plugins/text/plugin_data.proto",
+                "tensorbored/plugins/pr_curve/plugin_data.proto",
+                "tensorbored/plugins/mesh/plugin_data.proto",
+                "tensorbored/plugins/hparams/plugin_data.proto",
+                // Vendored from https://github.com/onnx/onnx; used by `onnx::convert` to read
+                // `ModelProto`/`GraphProto` without depending on a TensorFlow frontend.
+                "third_party/onnx/onnx.proto",
             ],
             &["."],
         )
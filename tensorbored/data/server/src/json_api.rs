@@ -0,0 +1,171 @@
+/* Copyright 2020 The TensorFlow Authors. All Rights Reserved.
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+==============================================================================*/
+
+//! Support for serializing generated `tensorbored` messages as JSON, so an HTTP layer can
+//! expose the same messages the gRPC `DataProvider` backend serves. Enabled by the
+//! `json-api` feature, which also turns on the `#[derive(serde::Serialize,
+//! serde::Deserialize)]` that `gen_protos_tool` attaches to every generated struct and enum.
+
+/// `serde(with = "base64_bytes")` support for `bytes::Bytes` fields, which have no built-in
+/// `serde` impl: encodes as a base64 string on the wire, matching the protobuf JSON mapping
+/// for the `bytes` scalar type.
+pub mod base64_bytes {
+    use base64::Engine as _;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &bytes::Bytes, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&base64::engine::general_purpose::STANDARD.encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<bytes::Bytes, D::Error> {
+        let encoded = String::deserialize(deserializer)?;
+        base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .map(bytes::Bytes::from)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// `serde(with = "base64_bytes_vec")` support for `repeated bytes` fields (e.g.
+/// `TensorProto.string_val`), which generate a `Vec<bytes::Bytes>` that the scalar
+/// [`base64_bytes`] adapter can't drive on its own: encodes each element as a base64
+/// string, matching the protobuf JSON mapping for `repeated bytes`.
+pub mod base64_bytes_vec {
+    use base64::Engine as _;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(values: &[bytes::Bytes], serializer: S) -> Result<S::Ok, S::Error> {
+        values
+            .iter()
+            .map(|v| base64::engine::general_purpose::STANDARD.encode(v))
+            .collect::<Vec<_>>()
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<bytes::Bytes>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .into_iter()
+            .map(|encoded| {
+                base64::engine::general_purpose::STANDARD
+                    .decode(encoded)
+                    .map(bytes::Bytes::from)
+                    .map_err(serde::de::Error::custom)
+            })
+            .collect()
+    }
+}
+
+/// `serde(with = "data_type_name")` support for `TensorProto.dtype`: prost represents
+/// `enum` fields as a plain `i32`, so the default `serde` derive would emit the wire number
+/// instead of the protobuf enum name (`DT_FLOAT`, `DT_INT32`, ...). This renders and parses
+/// the symbolic name instead, matching the protobuf JSON mapping for enums. `DataType`
+/// values outside this small table (e.g. `DT_BFLOAT16`, `DT_QINT8`) serialize as their raw
+/// wire number instead of being silently folded into `DT_INVALID`.
+pub mod data_type_name {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    const NAMES: &[(i32, &str)] = &[
+        (0, "DT_INVALID"),
+        (1, "DT_FLOAT"),
+        (2, "DT_DOUBLE"),
+        (3, "DT_INT32"),
+        (4, "DT_UINT8"),
+        (5, "DT_INT16"),
+        (6, "DT_INT8"),
+        (7, "DT_STRING"),
+        (8, "DT_COMPLEX64"),
+        (9, "DT_INT64"),
+        (10, "DT_BOOL"),
+    ];
+
+    pub fn serialize<S: Serializer>(value: &i32, serializer: S) -> Result<S::Ok, S::Error> {
+        match NAMES.iter().find(|(n, _)| n == value) {
+            Some((_, name)) => serializer.serialize_str(name),
+            None => serializer.serialize_i32(*value),
+        }
+    }
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum NameOrNumber {
+        Name(String),
+        Number(i32),
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i32, D::Error> {
+        match NameOrNumber::deserialize(deserializer)? {
+            NameOrNumber::Number(n) => Ok(n),
+            NameOrNumber::Name(name) => NAMES
+                .iter()
+                .find(|(_, candidate)| *candidate == name)
+                .map(|(n, _)| *n)
+                .ok_or_else(|| serde::de::Error::custom(format!("unknown DataType {name:?}"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize)]
+    struct Wrapper {
+        #[serde(with = "base64_bytes")]
+        data: bytes::Bytes,
+        #[serde(with = "base64_bytes_vec")]
+        data_list: Vec<bytes::Bytes>,
+        #[serde(with = "data_type_name")]
+        dtype: i32,
+    }
+
+    fn wrapper(dtype: i32) -> Wrapper {
+        Wrapper {
+            data: bytes::Bytes::from_static(b"\x00\x01\xff"),
+            data_list: vec![bytes::Bytes::from_static(b"a"), bytes::Bytes::from_static(b"\xff\x00")],
+            dtype,
+        }
+    }
+
+    #[test]
+    fn base64_bytes_round_trips() {
+        let wrapper = wrapper(1);
+        let json = serde_json::to_string(&wrapper).unwrap();
+        assert!(json.contains("\"dtype\":\"DT_FLOAT\""));
+        let parsed: Wrapper = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.data, wrapper.data);
+        assert_eq!(parsed.data_list, wrapper.data_list);
+        assert_eq!(parsed.dtype, wrapper.dtype);
+    }
+
+    #[test]
+    fn data_type_name_rejects_unknown_name() {
+        let err = serde_json::from_str::<Wrapper>(
+            r#"{"data":"AA==","data_list":[],"dtype":"DT_NOT_A_REAL_TYPE"}"#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("unknown DataType"));
+    }
+
+    #[test]
+    fn data_type_name_round_trips_unrecognized_numbers_instead_of_corrupting_them() {
+        // DT_BFLOAT16 in real TensorBoard protos; not in our small table.
+        let wrapper = wrapper(14);
+        let json = serde_json::to_string(&wrapper).unwrap();
+        assert!(json.contains("\"dtype\":14"), "unrecognized dtype should serialize as its number:\n{json}");
+        let parsed: Wrapper = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.dtype, 14);
+    }
+}
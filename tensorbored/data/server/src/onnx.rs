@@ -0,0 +1,197 @@
+/* Copyright 2020 The TensorFlow Authors. All Rights Reserved.
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+==============================================================================*/
+
+//! Converts an ONNX `ModelProto` into the `GraphDef` the event reader produces, so the
+//! existing graph plugin can render models from non-TensorFlow frameworks unchanged.
+//!
+//! The mapping is structural, not semantic: it does not interpret ops, only relabels them.
+//!
+//!  - each ONNX node becomes a graph node keyed by its `name` (synthesizing one from its
+//!    outputs if absent, since `name` is optional in ONNX but required in `GraphDef`);
+//!  - `op_type` becomes the node's `op`;
+//!  - `input`/`output` become `input` edges, by ONNX's convention of naming edges after the
+//!    tensor that flows along them;
+//!  - each `initializer` becomes its own `Const` node, so downstream consumers that
+//!    reference it by name resolve the same way they would a TensorFlow constant; and
+//!  - `AttributeProto`s map into the node's `attr` map.
+
+/// Generated types for the `onnx` package. Kept in its own module, rather than spliced
+/// flat alongside [`tb`], because both packages declare a `TensorProto` (this one is
+/// `onnx.TensorProto`, an initializer's tensor payload; `tb::TensorProto` is the unrelated
+/// `tensorbored.TensorProto` from `compat/proto/tensor.proto`) — merging them would collide.
+mod onnx_proto {
+    include!("../genproto/onnx.rs");
+}
+
+/// Generated types for the `tensorbored` package, i.e. the `GraphDef` this module builds.
+mod tb {
+    include!("../genproto/tensorbored.rs");
+}
+
+use std::collections::HashMap;
+
+use onnx_proto::{attribute_proto, AttributeProto, GraphProto, ModelProto, NodeProto};
+use tb::{attr_value, AttrValue, GraphDef, NodeDef};
+use thiserror::Error;
+
+/// An error encountered while converting an ONNX model into a `GraphDef`.
+#[derive(Debug, Error)]
+pub enum ConvertError {
+    #[error("ModelProto has no graph")]
+    MissingGraph,
+}
+
+/// Converts an ONNX model's graph into a TensorFlow-style [`GraphDef`].
+pub fn convert(model: &ModelProto) -> Result<GraphDef, ConvertError> {
+    let graph = model.graph.as_ref().ok_or(ConvertError::MissingGraph)?;
+    let mut nodes = Vec::with_capacity(graph.initializer.len() + graph.node.len());
+
+    for initializer in &graph.initializer {
+        nodes.push(NodeDef { name: initializer.name.clone(), op: "Const".to_string(), ..Default::default() });
+    }
+
+    let node_name = |i: usize, node: &NodeProto| {
+        if node.name.is_empty() {
+            node.output.first().cloned().unwrap_or_else(|| format!("node_{i}"))
+        } else {
+            node.name.clone()
+        }
+    };
+    // An ONNX edge names a tensor, while a `GraphDef` input names the node producing it
+    // (`node` for its first output, `node:k` for its k-th), so map each output to its producer.
+    let mut producers = HashMap::new();
+    for (i, node) in graph.node.iter().enumerate() {
+        for (k, output) in node.output.iter().enumerate() {
+            let name = node_name(i, node);
+            producers.insert(output.as_str(), if k == 0 { name } else { format!("{name}:{k}") });
+        }
+    }
+
+    for (i, node) in graph.node.iter().enumerate() {
+        let name = node_name(i, node);
+        let attr = node
+            .attribute
+            .iter()
+            .map(|attribute| (attribute.name.clone(), convert_attr(attribute)))
+            .collect();
+        let input = node
+            .input
+            .iter()
+            .map(|input| producers.get(input.as_str()).cloned().unwrap_or_else(|| input.clone()))
+            .collect();
+        nodes.push(NodeDef { name, op: node.op_type.clone(), input, attr, ..Default::default() });
+    }
+
+    Ok(GraphDef { node: nodes, ..Default::default() })
+}
+
+fn convert_attr(attribute: &AttributeProto) -> AttrValue {
+    use attribute_proto::AttributeType;
+    let value = match attribute.r#type() {
+        AttributeType::Int => Some(attr_value::Value::I(attribute.i)),
+        AttributeType::Float => Some(attr_value::Value::F(attribute.f)),
+        AttributeType::String => Some(attr_value::Value::S(attribute.s.clone())),
+        AttributeType::Ints => Some(attr_value::Value::List(attr_value::ListValue {
+            i: attribute.ints.clone(),
+            ..Default::default()
+        })),
+        AttributeType::Floats => Some(attr_value::Value::List(attr_value::ListValue {
+            f: attribute.floats.clone(),
+            ..Default::default()
+        })),
+        AttributeType::Strings => Some(attr_value::Value::List(attr_value::ListValue {
+            s: attribute.strings.clone(),
+            ..Default::default()
+        })),
+        // Tensor, graph, and sparse-tensor attributes aren't needed to draw the graph
+        // topology; omit them rather than guess at a TensorFlow-shaped encoding.
+        _ => None,
+    };
+    AttrValue { value }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use onnx_proto::TensorProto;
+
+    fn attr(name: &str, r#type: attribute_proto::AttributeType, set: impl FnOnce(&mut AttributeProto)) -> AttributeProto {
+        let mut attribute =
+            AttributeProto { name: name.to_string(), r#type: r#type as i32, ..Default::default() };
+        set(&mut attribute);
+        attribute
+    }
+
+    #[test]
+    fn missing_graph_is_an_error_not_a_panic() {
+        let model = ModelProto::default();
+        assert!(matches!(convert(&model), Err(ConvertError::MissingGraph)));
+    }
+
+    #[test]
+    fn initializers_become_const_nodes_and_nodes_keep_their_topology() {
+        let model = ModelProto {
+            graph: Some(GraphProto {
+                initializer: vec![TensorProto { name: "weight".to_string(), ..Default::default() }],
+                node: vec![NodeProto {
+                    name: "conv1".to_string(),
+                    op_type: "Conv".to_string(),
+                    input: vec!["input".to_string(), "weight".to_string()],
+                    output: vec!["conv1_out".to_string()],
+                    attribute: vec![attr("strides", attribute_proto::AttributeType::Ints, |a| {
+                        a.ints = vec![1, 1]
+                    })],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let graph_def = convert(&model).expect("graph is present");
+        assert_eq!(graph_def.node.len(), 2);
+
+        let constant = &graph_def.node[0];
+        assert_eq!(constant.name, "weight");
+        assert_eq!(constant.op, "Const");
+
+        let conv = &graph_def.node[1];
+        assert_eq!(conv.name, "conv1");
+        assert_eq!(conv.op, "Conv");
+        assert_eq!(conv.input, vec!["input", "weight"]);
+        match &conv.attr["strides"].value {
+            Some(attr_value::Value::List(list)) => assert_eq!(list.i, vec![1, 1]),
+            other => panic!("expected a list attr, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn unnamed_node_falls_back_to_its_output_name() {
+        let model = ModelProto {
+            graph: Some(GraphProto {
+                node: vec![NodeProto {
+                    op_type: "Relu".to_string(),
+                    output: vec!["relu_out".to_string()],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let graph_def = convert(&model).expect("graph is present");
+        assert_eq!(graph_def.node[0].name, "relu_out");
+    }
+}
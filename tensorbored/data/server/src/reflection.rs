@@ -0,0 +1,35 @@
+/* Copyright 2020 The TensorFlow Authors. All Rights Reserved.
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+==============================================================================*/
+
+//! gRPC server reflection (`grpc.reflection.v1alpha.ServerReflection`), backed by the
+//! `FileDescriptorSet` that `gen_protos_tool` writes to `genproto/descriptor.bin`.
+//!
+//! Registering this service lets `grpcurl`/`grpc_cli` introspect and call `DataProvider`
+//! without the caller needing a copy of the `.proto` sources.
+
+use tonic_reflection::server::{ServerReflection, ServerReflectionServer};
+
+/// The encoded `FileDescriptorSet` produced at proto-compile time for the `tensorbored`
+/// package, including `data_provider.proto` and its transitive dependencies.
+static FILE_DESCRIPTOR_SET: &[u8] = include_bytes!("../genproto/descriptor.bin");
+
+/// Builds the reflection service to register on the `tonic` server builder alongside
+/// `DataProviderServer`.
+pub fn service() -> ServerReflectionServer<impl ServerReflection> {
+    tonic_reflection::server::Builder::configure()
+        .register_encoded_file_descriptor_set(FILE_DESCRIPTOR_SET)
+        .build()
+        .expect("failed to build gRPC reflection service from descriptor.bin")
+}
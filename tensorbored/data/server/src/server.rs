@@ -0,0 +1,29 @@
+/* Copyright 2020 The TensorFlow Authors. All Rights Reserved.
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+==============================================================================*/
+
+//! Assembles the `tonic` server that serves `DataProvider`.
+
+use tonic::transport::server::{Router, Server};
+
+include!("../genproto/tensorbored.data.rs");
+
+/// Builds the `tonic` router serving `data_provider`, with gRPC reflection registered
+/// alongside it when the `reflection` feature is enabled.
+pub fn router(data_provider: impl data_provider_server::DataProvider) -> Router {
+    let router = Server::builder().add_service(data_provider_server::DataProviderServer::new(data_provider));
+    #[cfg(feature = "reflection")]
+    let router = router.add_service(crate::reflection::service());
+    router
+}
@@ -0,0 +1,474 @@
+/* Copyright 2020 The TensorFlow Authors. All Rights Reserved.
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    http://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+==============================================================================*/
+
+//! Text-format (`.pbtxt`) codec for `tensorbored` messages.
+//!
+//! TensorBoard log directories routinely contain text-format protobufs (`graph.pbtxt`,
+//! projector `config.pbtxt`, run metadata) that `prost` cannot parse on its own, since it
+//! only speaks the binary wire format. This module drives a [`DynamicMessage`] off the
+//! `FileDescriptorSet` that `gen_protos_tool` writes to `genproto/descriptor.bin`, so it
+//! can print and parse any message in the `tensorbored` package without per-message
+//! generated code.
+
+use std::fmt::Write as _;
+
+use prost_reflect::{DescriptorPool, DynamicMessage, Kind, MessageDescriptor, Value};
+use thiserror::Error;
+
+/// The descriptor pool for the `tensorbored` package, built from the `FileDescriptorSet`
+/// that `gen_protos_tool` writes alongside the generated Rust types.
+static DESCRIPTOR_SET: &[u8] = include_bytes!("../genproto/descriptor.bin");
+
+fn descriptor_pool() -> &'static DescriptorPool {
+    use std::sync::OnceLock;
+    static POOL: OnceLock<DescriptorPool> = OnceLock::new();
+    POOL.get_or_init(|| {
+        DescriptorPool::decode(DESCRIPTOR_SET).expect("genproto/descriptor.bin is not a valid FileDescriptorSet")
+    })
+}
+
+/// An error encountered while parsing text-format input.
+#[derive(Debug, Error)]
+pub enum ParseError {
+    #[error("unexpected end of input")]
+    UnexpectedEof,
+    #[error("unexpected token {found:?}, expected {expected}")]
+    UnexpectedToken { found: String, expected: &'static str },
+    #[error("message {0:?} has no field named {1:?}")]
+    UnknownField(String, String),
+    #[error("{0:?} is not a valid value for field {1:?}")]
+    InvalidValue(String, String),
+    #[error("field {0:?} is not valid UTF-8: {1}")]
+    InvalidUtf8(String, std::str::Utf8Error),
+    #[error("no message named {0:?} in genproto/descriptor.bin")]
+    UnknownMessage(String),
+}
+
+/// Looks up a message type by its fully qualified name (e.g. `tensorbored.GraphDef`) in the
+/// descriptor set `gen_protos_tool` writes to `genproto/descriptor.bin`.
+pub fn message_descriptor(full_name: &str) -> Result<MessageDescriptor, ParseError> {
+    descriptor_pool()
+        .get_message_by_name(full_name)
+        .ok_or_else(|| ParseError::UnknownMessage(full_name.to_string()))
+}
+
+/// Renders `msg` as text-format protobuf, the same syntax `google.protobuf.TextFormat`
+/// produces: `field_name: scalar` lines, nested messages as `field_name { ... }` blocks,
+/// one line per element for repeated fields, enums by their symbolic name, and quoted,
+/// escaped strings.
+pub fn to_text(msg: &DynamicMessage) -> String {
+    let mut out = String::new();
+    write_message(&mut out, msg, 0);
+    out
+}
+
+fn write_message(out: &mut String, msg: &DynamicMessage, indent: usize) {
+    for field in msg.descriptor().fields() {
+        if field.is_list() {
+            for value in msg.get_field(&field).as_list().into_iter().flatten() {
+                write_field(out, &field, value, indent);
+            }
+        } else if msg.has_field(&field) {
+            write_field(out, &field, &msg.get_field(&field), indent);
+        }
+    }
+}
+
+fn write_field(out: &mut String, field: &prost_reflect::FieldDescriptor, value: &Value, indent: usize) {
+    let pad = "  ".repeat(indent);
+    match value {
+        Value::Message(nested) => {
+            // No colon before a nested-message block: `parse_message` only takes the
+            // message branch when `{` immediately follows the field name, matching
+            // `google.protobuf.TextFormat`'s `field_name { ... }` syntax.
+            let _ = writeln!(out, "{pad}{} {{", field.name());
+            write_message(out, nested, indent + 1);
+            let _ = writeln!(out, "{pad}}}");
+        }
+        Value::EnumNumber(n) => {
+            let name = match field.kind() {
+                Kind::Enum(e) => e
+                    .get_value(*n)
+                    .map(|v| v.name().to_string())
+                    .unwrap_or_else(|| n.to_string()),
+                _ => n.to_string(),
+            };
+            let _ = writeln!(out, "{pad}{}: {}", field.name(), name);
+        }
+        Value::String(s) => {
+            let _ = writeln!(out, "{pad}{}: \"{}\"", field.name(), escape_bytes(s.as_bytes()));
+        }
+        Value::Bytes(b) => {
+            let _ = writeln!(out, "{pad}{}: \"{}\"", field.name(), escape_bytes(b));
+        }
+        other => {
+            let _ = writeln!(out, "{pad}{}: {}", field.name(), other);
+        }
+    }
+}
+
+/// Escapes a byte string the way `google.protobuf.TextFormat` does: printable ASCII is kept
+/// literal (quotes and backslashes aside), newline, carriage return and tab become `\n`,
+/// `\r` and `\t`, and every other byte — including non-UTF-8 bytes in a `bytes` field —
+/// becomes a three-digit octal escape, so the output round-trips
+/// exactly through [`from_text`] regardless of the field's encoding.
+fn escape_bytes(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len());
+    for &b in bytes {
+        match b {
+            b'\\' => out.push_str("\\\\"),
+            b'"' => out.push_str("\\\""),
+            b'\n' => out.push_str("\\n"),
+            b'\r' => out.push_str("\\r"),
+            b'\t' => out.push_str("\\t"),
+            0x20..=0x7e => out.push(b as char),
+            _ => {
+                let _ = write!(out, "\\{b:03o}");
+            }
+        }
+    }
+    out
+}
+
+/// Parses `input` as text-format protobuf for the named message type (e.g.
+/// `tensorbored.GraphDef`), looked up in the same descriptor set `to_text` reads.
+///
+/// Unknown fields are rejected rather than silently dropped, `oneof` fields are resolved
+/// like any other field on the containing message, and enum values may be given either
+/// symbolically or numerically.
+pub fn from_text(message_name: &str, input: &str) -> Result<DynamicMessage, ParseError> {
+    from_text_with_descriptor(message_descriptor(message_name)?, input)
+}
+
+/// Like [`from_text`], but for a descriptor the caller already has in hand rather than one
+/// looked up by name in the crate's global descriptor pool.
+pub fn from_text_with_descriptor(descriptor: MessageDescriptor, input: &str) -> Result<DynamicMessage, ParseError> {
+    let tokens = tokenize(input);
+    let mut parser = Parser { tokens: &tokens, pos: 0 };
+    parser.parse_message(descriptor)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(Vec<u8>),
+    Number(String),
+    Colon,
+    LBrace,
+    RBrace,
+}
+
+fn tokenize(input: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() || c == '#' => {
+                if c == '#' {
+                    for c in chars.by_ref() {
+                        if c == '\n' {
+                            break;
+                        }
+                    }
+                } else {
+                    chars.next();
+                }
+            }
+            ':' => {
+                chars.next();
+                tokens.push(Token::Colon);
+            }
+            '{' => {
+                chars.next();
+                tokens.push(Token::LBrace);
+            }
+            '}' => {
+                chars.next();
+                tokens.push(Token::RBrace);
+            }
+            '"' => {
+                chars.next();
+                let mut bytes = Vec::new();
+                loop {
+                    match chars.next() {
+                        Some('"') | None => break,
+                        Some('\\') => match chars.next() {
+                            Some('n') => bytes.push(b'\n'),
+                            Some('r') => bytes.push(b'\r'),
+                            Some('t') => bytes.push(b'\t'),
+                            Some('"') => bytes.push(b'"'),
+                            Some('\'') => bytes.push(b'\''),
+                            Some('\\') => bytes.push(b'\\'),
+                            Some('x') => {
+                                let mut hex = String::new();
+                                while hex.len() < 2 && chars.peek().is_some_and(char::is_ascii_hexdigit) {
+                                    hex.push(chars.next().unwrap());
+                                }
+                                bytes.push(u8::from_str_radix(&hex, 16).unwrap_or(0));
+                            }
+                            Some(d) if d.is_digit(8) => {
+                                let mut octal = String::from(d);
+                                while octal.len() < 3 && chars.peek().is_some_and(|c| c.is_digit(8)) {
+                                    octal.push(chars.next().unwrap());
+                                }
+                                bytes.push(u8::from_str_radix(&octal, 8).unwrap_or(0));
+                            }
+                            Some(other) => {
+                                let mut buf = [0u8; 4];
+                                bytes.extend_from_slice(other.encode_utf8(&mut buf).as_bytes());
+                            }
+                            None => break,
+                        },
+                        Some(c) => {
+                            let mut buf = [0u8; 4];
+                            bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+                        }
+                    }
+                }
+                tokens.push(Token::Str(bytes));
+            }
+            c if c.is_ascii_digit() || c == '-' || c == '+' => {
+                let mut s = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_ascii_alphanumeric() || c == '.' || c == '-' || c == '+' {
+                        s.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token::Number(s));
+            }
+            _ => {
+                let mut s = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_alphanumeric() || c == '_' {
+                        s.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token::Ident(s));
+            }
+        }
+    }
+    tokens
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Result<Token, ParseError> {
+        let tok = self.tokens.get(self.pos).cloned().ok_or(ParseError::UnexpectedEof)?;
+        self.pos += 1;
+        Ok(tok)
+    }
+
+    fn expect(&mut self, expected: Token, what: &'static str) -> Result<(), ParseError> {
+        match self.next()? {
+            tok if tok == expected => Ok(()),
+            found => Err(ParseError::UnexpectedToken { found: format!("{found:?}"), expected: what }),
+        }
+    }
+
+    fn parse_message(&mut self, descriptor: prost_reflect::MessageDescriptor) -> Result<DynamicMessage, ParseError> {
+        let mut msg = DynamicMessage::new(descriptor.clone());
+        while let Some(Token::Ident(_)) = self.peek() {
+            let name = match self.next()? {
+                Token::Ident(name) => name,
+                _ => unreachable!(),
+            };
+            let field = descriptor
+                .get_field_by_name(&name)
+                .ok_or_else(|| ParseError::UnknownField(descriptor.full_name().to_string(), name.clone()))?;
+            match self.peek() {
+                Some(Token::LBrace) => {
+                    self.next()?;
+                    let Kind::Message(nested_descriptor) = field.kind() else {
+                        return Err(ParseError::InvalidValue("{ ... }".into(), name));
+                    };
+                    let nested = self.parse_message(nested_descriptor)?;
+                    self.expect(Token::RBrace, "}")?;
+                    if field.is_list() {
+                        msg.get_field_mut(&field)
+                            .as_list_mut()
+                            .expect("list field")
+                            .push(Value::Message(nested));
+                    } else {
+                        msg.set_field(&field, Value::Message(nested));
+                    }
+                }
+                _ => {
+                    self.expect(Token::Colon, ":")?;
+                    let value = self.parse_scalar(&field)?;
+                    if field.is_list() {
+                        msg.get_field_mut(&field).as_list_mut().expect("list field").push(value);
+                    } else {
+                        msg.set_field(&field, value);
+                    }
+                }
+            }
+        }
+        Ok(msg)
+    }
+
+    fn parse_scalar(&mut self, field: &prost_reflect::FieldDescriptor) -> Result<Value, ParseError> {
+        let tok = self.next()?;
+        match (field.kind(), tok) {
+            (Kind::String, Token::Str(b)) => String::from_utf8(b)
+                .map(Value::String)
+                .map_err(|e| ParseError::InvalidUtf8(field.name().to_string(), e.utf8_error())),
+            (Kind::Bytes, Token::Str(b)) => Ok(Value::Bytes(b.into())),
+            (Kind::Bool, Token::Ident(s)) => match s.as_str() {
+                "true" => Ok(Value::Bool(true)),
+                "false" => Ok(Value::Bool(false)),
+                other => Err(ParseError::InvalidValue(other.to_string(), field.name().to_string())),
+            },
+            (Kind::Enum(e), Token::Ident(s)) => e
+                .get_value_by_name(&s)
+                .map(|v| Value::EnumNumber(v.number()))
+                .ok_or_else(|| ParseError::InvalidValue(s, field.name().to_string())),
+            (Kind::Enum(_), Token::Number(n)) => n
+                .parse::<i32>()
+                .map(Value::EnumNumber)
+                .map_err(|_| ParseError::InvalidValue(n, field.name().to_string())),
+            (Kind::Int32, Token::Number(n)) | (Kind::Sint32, Token::Number(n)) | (Kind::Sfixed32, Token::Number(n)) => {
+                n.parse().map(Value::I32).map_err(|_| ParseError::InvalidValue(n, field.name().to_string()))
+            }
+            (Kind::Int64, Token::Number(n)) | (Kind::Sint64, Token::Number(n)) | (Kind::Sfixed64, Token::Number(n)) => {
+                n.parse().map(Value::I64).map_err(|_| ParseError::InvalidValue(n, field.name().to_string()))
+            }
+            (Kind::Uint32, Token::Number(n)) | (Kind::Fixed32, Token::Number(n)) => {
+                n.parse().map(Value::U32).map_err(|_| ParseError::InvalidValue(n, field.name().to_string()))
+            }
+            (Kind::Uint64, Token::Number(n)) | (Kind::Fixed64, Token::Number(n)) => {
+                n.parse().map(Value::U64).map_err(|_| ParseError::InvalidValue(n, field.name().to_string()))
+            }
+            (Kind::Float, Token::Number(n)) => {
+                n.parse().map(Value::F32).map_err(|_| ParseError::InvalidValue(n, field.name().to_string()))
+            }
+            (Kind::Double, Token::Number(n)) => {
+                n.parse().map(Value::F64).map_err(|_| ParseError::InvalidValue(n, field.name().to_string()))
+            }
+            (_, other) => Err(ParseError::UnexpectedToken { found: format!("{other:?}"), expected: "scalar value" }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+    use prost_reflect::prost_types::{
+        DescriptorProto, EnumDescriptorProto, EnumValueDescriptorProto, FieldDescriptorProto, FileDescriptorProto,
+        FileDescriptorSet,
+    };
+    use prost_reflect::prost_types::field_descriptor_proto::{Label, Type};
+
+    /// Builds a tiny, self-contained `test.Msg` descriptor (a string, a bytes, an enum, and
+    /// a self-referential nested message field) so these tests don't depend on which
+    /// messages `genproto/descriptor.bin` holds (the module still embeds that file, so it
+    /// must exist for the tests to compile).
+    fn test_descriptor() -> MessageDescriptor {
+        let field = |name: &str, number: i32, r#type: Type, type_name: Option<&str>| FieldDescriptorProto {
+            name: Some(name.to_string()),
+            number: Some(number),
+            label: Some(Label::Optional as i32),
+            r#type: Some(r#type as i32),
+            type_name: type_name.map(str::to_string),
+            ..Default::default()
+        };
+        let file = FileDescriptorProto {
+            name: Some("test.proto".to_string()),
+            package: Some("test".to_string()),
+            syntax: Some("proto3".to_string()),
+            message_type: vec![DescriptorProto {
+                name: Some("Msg".to_string()),
+                field: vec![
+                    field("name", 1, Type::String, None),
+                    field("blob", 2, Type::Bytes, None),
+                    field("color", 3, Type::Enum, Some(".test.Color")),
+                    field("nested", 4, Type::Message, Some(".test.Msg")),
+                ],
+                ..Default::default()
+            }],
+            enum_type: vec![EnumDescriptorProto {
+                name: Some("Color".to_string()),
+                value: vec![
+                    EnumValueDescriptorProto { name: Some("UNKNOWN".to_string()), number: Some(0) },
+                    EnumValueDescriptorProto { name: Some("RED".to_string()), number: Some(1) },
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let pool = DescriptorPool::from_file_descriptor_set(FileDescriptorSet { file: vec![file] })
+            .expect("test descriptor set is well-formed");
+        pool.get_message_by_name("test.Msg").expect("test.Msg is registered")
+    }
+
+    #[test]
+    fn round_trips_through_text() {
+        let descriptor = test_descriptor();
+        let mut msg = DynamicMessage::new(descriptor.clone());
+        msg.set_field_by_name("name", Value::String("hello \"world\"".to_string()));
+        msg.set_field_by_name("blob", Value::Bytes(Bytes::from_static(b"\x00\x01\xff")));
+        msg.set_field_by_name("color", Value::EnumNumber(1));
+        let mut nested = DynamicMessage::new(descriptor.clone());
+        nested.set_field_by_name("name", Value::String("child".to_string()));
+        msg.set_field_by_name("nested", Value::Message(nested));
+
+        let text = to_text(&msg);
+        assert!(text.contains("color: RED"), "enum should print symbolically:\n{text}");
+        assert!(text.contains("nested {"), "nested message should print as a block:\n{text}");
+
+        let parsed = from_text_with_descriptor(descriptor, &text).expect("round-trip parse");
+        assert_eq!(parsed, msg);
+    }
+
+    #[test]
+    fn numeric_enum_value_resolves_to_symbolic_name() {
+        let descriptor = test_descriptor();
+        let parsed = from_text_with_descriptor(descriptor, "color: 1").unwrap();
+        assert_eq!(parsed.get_field_by_name("color").unwrap().as_enum_number(), Some(1));
+        assert!(to_text(&parsed).contains("color: RED"));
+    }
+
+    #[test]
+    fn unknown_field_is_rejected_not_dropped() {
+        let descriptor = test_descriptor();
+        let err = from_text_with_descriptor(descriptor, "bogus_field: 1").unwrap_err();
+        assert!(matches!(err, ParseError::UnknownField(_, field) if field == "bogus_field"));
+    }
+
+    #[test]
+    fn non_utf8_bytes_round_trip_through_octal_escapes() {
+        let descriptor = test_descriptor();
+        let mut msg = DynamicMessage::new(descriptor.clone());
+        msg.set_field_by_name("blob", Value::Bytes(Bytes::from_static(&[0xff, 0x00, b'"', b'\\'])));
+
+        let text = to_text(&msg);
+        let parsed = from_text_with_descriptor(descriptor, &text).expect("round-trip parse");
+        assert_eq!(parsed, msg);
+    }
+}